@@ -26,69 +26,333 @@ use std::{
     sync::atomic::{AtomicBool, Ordering},
 };
 
+#[cfg(feature = "tokio")]
+mod tokio_io;
+
+/// Something that can tell an [`Interruptable`] it should stop.
+///
+/// Implement this for whatever you want to watch -- a flag flipped by a
+/// signal handler, a deadline, a counter bumped on each signal, a closure --
+/// and build an [`Interruptable`] around it. A blanket impl is provided for
+/// `AsRef<AtomicBool>`, which is how this crate worked before this trait
+/// existed.
+pub trait InterruptSource {
+    /// Returns `true` once the wrapped operation should be aborted.
+    fn is_interrupted(&self) -> bool;
+}
+
+impl<T: AsRef<AtomicBool>> InterruptSource for T {
+    #[inline]
+    fn is_interrupted(&self) -> bool {
+        self.as_ref().load(Ordering::SeqCst)
+    }
+}
+
+/// Why an [`Interruptable`] call was cut short.
+///
+/// This is the inner error carried by the [`io::Error`]s that
+/// [`Interruptable`] returns when it cuts a call short, so callers can tell
+/// "the user cancelled" apart from a genuine I/O failure and, if they care,
+/// tell the two cancellation points apart too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptError {
+    /// The interrupt source had already fired before the inner I/O was
+    /// even called.
+    CancelledBeforeCall,
+    /// The inner I/O returned [`io::ErrorKind::Interrupted`], and on
+    /// checking, the interrupt source had fired too.
+    CancelledDuringCall,
+}
+
+impl std::fmt::Display for InterruptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CancelledBeforeCall => write!(f, "cancelled before the call started"),
+            Self::CancelledDuringCall => write!(f, "cancelled while the call was in progress"),
+        }
+    }
+}
+
+impl std::error::Error for InterruptError {}
+
+impl From<InterruptError> for io::Error {
+    #[inline]
+    fn from(reason: InterruptError) -> Self {
+        io::Error::other(reason)
+    }
+}
+
+impl InterruptError {
+    /// Recovers the [`InterruptError`] from an [`io::Error`], if that's what
+    /// produced it -- e.g. to distinguish cancellation from a genuine
+    /// underlying failure.
+    pub fn downcast(e: &io::Error) -> Option<Self> {
+        e.get_ref()?.downcast_ref::<Self>().copied()
+    }
+}
+
 /** See crate-level documentation for more info. */
 pub struct Interruptable<IO, H> {
     inner: IO,
     interrupt_flag: H,
+    chunk_size: Option<usize>,
 }
 
-impl<IO, H: AsRef<AtomicBool>> Interruptable<IO, H> {
+impl<IO, H: InterruptSource> Interruptable<IO, H> {
     #[inline]
     pub fn new(inner: IO, interrupt_flag: H) -> Self {
         Self {
             inner,
             interrupt_flag,
+            chunk_size: None,
+        }
+    }
+
+    /// Like [`Self::new`], but bulk transfers -- `read`, `write`,
+    /// `read_vectored`, `write_vectored`, and `read_to_end` -- are performed
+    /// in chunks of at most `chunk_size` bytes, checking the interrupt
+    /// source between chunks. Interrupting mid-transfer returns `Ok(n)` for
+    /// the bytes already moved rather than an error -- it only errors once
+    /// nothing has moved yet -- so callers can resume from offset `n`.
+    ///
+    /// `chunk_size` has no effect on [`io::BufRead::fill_buf`] or
+    /// [`io::Seek::seek`]: neither moves a caller-bounded amount of data, so
+    /// there is no "partial progress" to report for them.
+    #[inline]
+    pub fn with_partial_progress(inner: IO, interrupt_flag: H, chunk_size: usize) -> Self {
+        Self {
+            inner,
+            interrupt_flag,
+            chunk_size: Some(chunk_size.max(1)),
         }
     }
 
     #[inline]
-    fn check_again(&self, e: io::Error) -> io::Error {
+    fn check_again(flag: &H, e: io::Error) -> io::Error {
         if e.kind() == io::ErrorKind::Interrupted
             // It can be interrupted by other signal, so let's check the flag...
-            && self.interrupt_flag.as_ref().load(Ordering::SeqCst)
+            && flag.is_interrupted()
         {
-            Self::das_error()
+            Self::das_error(InterruptError::CancelledDuringCall)
         } else {
             e
         }
     }
 
     #[inline]
-    fn das_error() -> io::Error {
-        io::Error::new(
-            io::ErrorKind::Other,
-            io::Error::from(io::ErrorKind::Interrupted),
-        )
+    fn das_error(reason: InterruptError) -> io::Error {
+        io::Error::from(reason)
+    }
+}
+
+impl<IO: io::Read, H: InterruptSource> Interruptable<IO, H> {
+    /// Reads `buf` in chunks of at most `chunk_size` bytes, returning the
+    /// bytes read so far on interruption instead of an error once at least
+    /// one byte has moved.
+    fn read_in_chunks(&mut self, buf: &mut [u8], chunk_size: usize) -> io::Result<usize> {
+        let mut total = 0;
+        while total < buf.len() {
+            if self.interrupt_flag.is_interrupted() {
+                return if total == 0 {
+                    Err(Self::das_error(InterruptError::CancelledBeforeCall))
+                } else {
+                    Ok(total)
+                };
+            }
+
+            let end = std::cmp::min(total + chunk_size, buf.len());
+            match self.inner.read(&mut buf[total..end]) {
+                Ok(0) => break,
+                Ok(n) => total += n,
+                // Whatever the error, `total` bytes are already sitting in
+                // `buf`, so `Read::read`'s "a partial read is reported"
+                // contract means we must hand those back rather than
+                // claiming nothing happened.
+                Err(_) if total > 0 => return Ok(total),
+                Err(e) => return Err(Self::check_again(&self.interrupt_flag, e)),
+            }
+        }
+        Ok(total)
+    }
+
+    /// Like [`Self::read_in_chunks`], but appends to a growing `Vec` the way
+    /// [`io::Read::read_to_end`] does.
+    fn read_to_end_in_chunks(&mut self, buf: &mut Vec<u8>, chunk_size: usize) -> io::Result<usize> {
+        let start_len = buf.len();
+        let mut chunk = vec![0; chunk_size];
+        loop {
+            if self.interrupt_flag.is_interrupted() {
+                return if buf.len() > start_len {
+                    Ok(buf.len() - start_len)
+                } else {
+                    Err(Self::das_error(InterruptError::CancelledBeforeCall))
+                };
+            }
+
+            match self.inner.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                Err(_) if buf.len() > start_len => return Ok(buf.len() - start_len),
+                Err(e) => return Err(Self::check_again(&self.interrupt_flag, e)),
+            }
+        }
+        Ok(buf.len() - start_len)
     }
 }
 
-impl<IO: io::Read, H: AsRef<AtomicBool>> io::Read for Interruptable<IO, H> {
+impl<IO: io::Read, H: InterruptSource> io::Read for Interruptable<IO, H> {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        if self.interrupt_flag.as_ref().load(Ordering::SeqCst) {
-            Err(Self::das_error())
+        if let Some(chunk_size) = self.chunk_size {
+            return self.read_in_chunks(buf, chunk_size);
+        }
+
+        if self.interrupt_flag.is_interrupted() {
+            Err(Self::das_error(InterruptError::CancelledBeforeCall))
+        } else {
+            self.inner
+                .read(buf)
+                .map_err(|e| Self::check_again(&self.interrupt_flag, e))
+        }
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+        if let Some(chunk_size) = self.chunk_size {
+            return match bufs.iter_mut().find(|b| !b.is_empty()) {
+                Some(buf) => self.read_in_chunks(buf, chunk_size),
+                None => Ok(0),
+            };
+        }
+
+        if self.interrupt_flag.is_interrupted() {
+            Err(Self::das_error(InterruptError::CancelledBeforeCall))
+        } else {
+            self.inner
+                .read_vectored(bufs)
+                .map_err(|e| Self::check_again(&self.interrupt_flag, e))
+        }
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        if let Some(chunk_size) = self.chunk_size {
+            return self.read_to_end_in_chunks(buf, chunk_size);
+        }
+
+        if self.interrupt_flag.is_interrupted() {
+            Err(Self::das_error(InterruptError::CancelledBeforeCall))
         } else {
-            self.inner.read(buf).map_err(|e| self.check_again(e))
+            self.inner
+                .read_to_end(buf)
+                .map_err(|e| Self::check_again(&self.interrupt_flag, e))
         }
     }
 }
 
-impl<IO: io::Write, H: AsRef<AtomicBool>> io::Write for Interruptable<IO, H> {
+impl<IO: io::Write, H: InterruptSource> Interruptable<IO, H> {
+    /// Writes `buf` in chunks of at most `chunk_size` bytes, returning the
+    /// bytes written so far on interruption instead of an error once at
+    /// least one byte has moved.
+    fn write_in_chunks(&mut self, buf: &[u8], chunk_size: usize) -> io::Result<usize> {
+        let mut total = 0;
+        while total < buf.len() {
+            if self.interrupt_flag.is_interrupted() {
+                return if total == 0 {
+                    Err(Self::das_error(InterruptError::CancelledBeforeCall))
+                } else {
+                    Ok(total)
+                };
+            }
+
+            let end = std::cmp::min(total + chunk_size, buf.len());
+            match self.inner.write(&buf[total..end]) {
+                Ok(0) => break,
+                Ok(n) => total += n,
+                // Whatever the error, `total` bytes have already been
+                // accepted by the inner writer, so reporting `Err` here would
+                // make a caller who retries the whole buffer duplicate them.
+                Err(_) if total > 0 => return Ok(total),
+                Err(e) => return Err(Self::check_again(&self.interrupt_flag, e)),
+            }
+        }
+        Ok(total)
+    }
+}
+
+impl<IO: io::Write, H: InterruptSource> io::Write for Interruptable<IO, H> {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        if self.interrupt_flag.as_ref().load(Ordering::SeqCst) {
-            Err(Self::das_error())
+        if let Some(chunk_size) = self.chunk_size {
+            return self.write_in_chunks(buf, chunk_size);
+        }
+
+        if self.interrupt_flag.is_interrupted() {
+            Err(Self::das_error(InterruptError::CancelledBeforeCall))
+        } else {
+            self.inner
+                .write(buf)
+                .map_err(|e| Self::check_again(&self.interrupt_flag, e))
+        }
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        if let Some(chunk_size) = self.chunk_size {
+            return match bufs.iter().find(|b| !b.is_empty()) {
+                Some(buf) => self.write_in_chunks(buf, chunk_size),
+                None => Ok(0),
+            };
+        }
+
+        if self.interrupt_flag.is_interrupted() {
+            Err(Self::das_error(InterruptError::CancelledBeforeCall))
         } else {
-            self.inner.write(buf).map_err(|e| self.check_again(e))
+            self.inner
+                .write_vectored(bufs)
+                .map_err(|e| Self::check_again(&self.interrupt_flag, e))
         }
     }
 
     #[inline]
     fn flush(&mut self) -> io::Result<()> {
-        if self.interrupt_flag.as_ref().load(Ordering::SeqCst) {
-            Err(Self::das_error())
+        if self.interrupt_flag.is_interrupted() {
+            Err(Self::das_error(InterruptError::CancelledBeforeCall))
         } else {
-            self.inner.flush().map_err(|e| self.check_again(e))
+            self.inner
+                .flush()
+                .map_err(|e| Self::check_again(&self.interrupt_flag, e))
+        }
+    }
+}
+
+impl<IO: io::BufRead, H: InterruptSource> io::BufRead for Interruptable<IO, H> {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.interrupt_flag.is_interrupted() {
+            return Err(Self::das_error(InterruptError::CancelledBeforeCall));
+        }
+        match self.inner.fill_buf() {
+            Ok(buf) => Ok(buf),
+            Err(e) => Err(Self::check_again(&self.interrupt_flag, e)),
+        }
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+    }
+}
+
+impl<IO: io::Seek, H: InterruptSource> io::Seek for Interruptable<IO, H> {
+    #[inline]
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        if self.interrupt_flag.is_interrupted() {
+            Err(Self::das_error(InterruptError::CancelledBeforeCall))
+        } else {
+            self.inner
+                .seek(pos)
+                .map_err(|e| Self::check_again(&self.interrupt_flag, e))
         }
     }
 }
@@ -96,14 +360,14 @@ impl<IO: io::Write, H: AsRef<AtomicBool>> io::Write for Interruptable<IO, H> {
 #[cfg(test)]
 mod tests {
     use std::{
-        io::{self, ErrorKind, Read, Write},
+        io::{self, BufRead, ErrorKind, Read, Seek, Write},
         sync::{
             atomic::{AtomicBool, Ordering},
             Arc,
         },
     };
 
-    use crate::Interruptable;
+    use crate::{InterruptError, InterruptSource, Interruptable};
 
     struct Mock {
         value: Option<io::Result<Vec<u8>>>,
@@ -138,6 +402,9 @@ mod tests {
 
     impl Write for Mock {
         fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if let Some(int) = &self.interrupt {
+                int.store(true, Ordering::SeqCst);
+            }
             match self.value.take() {
                 Some(Ok(_)) => Ok(buf.len()),
                 Some(Err(e)) => Err(e),
@@ -184,7 +451,10 @@ mod tests {
 
         let e = inp.read(&mut buf).unwrap_err();
         assert_eq!(e.kind(), io::ErrorKind::Other);
-        assert!(e.get_ref().is_some());
+        assert_eq!(
+            InterruptError::downcast(&e),
+            Some(InterruptError::CancelledBeforeCall)
+        );
     }
 
     #[test]
@@ -200,11 +470,15 @@ mod tests {
             flag2,
         );
         let mut buf = vec![0; 42];
-        flag.store(true, Ordering::SeqCst);
 
+        // The flag flips only once the Mock's `read` runs, so this exercises
+        // `check_again`'s post-call check rather than the pre-call one.
         let e = inp.read(&mut buf).unwrap_err();
         assert_eq!(e.kind(), io::ErrorKind::Other);
-        assert!(e.get_ref().is_some());
+        assert_eq!(
+            InterruptError::downcast(&e),
+            Some(InterruptError::CancelledDuringCall)
+        );
     }
 
     #[test]
@@ -253,7 +527,10 @@ mod tests {
 
         let e = inp.write(&buf).unwrap_err();
         assert_eq!(e.kind(), io::ErrorKind::Other);
-        assert!(e.get_ref().is_some());
+        assert_eq!(
+            InterruptError::downcast(&e),
+            Some(InterruptError::CancelledBeforeCall)
+        );
     }
 
     #[test]
@@ -269,11 +546,15 @@ mod tests {
             flag2,
         );
         let buf = vec![0; 42];
-        flag.store(true, Ordering::SeqCst);
 
+        // The flag flips only once the Mock's `write` runs, so this exercises
+        // `check_again`'s post-call check rather than the pre-call one.
         let e = inp.write(&buf).unwrap_err();
         assert_eq!(e.kind(), io::ErrorKind::Other);
-        assert!(e.get_ref().is_some());
+        assert_eq!(
+            InterruptError::downcast(&e),
+            Some(InterruptError::CancelledDuringCall)
+        );
     }
 
     #[test]
@@ -289,4 +570,312 @@ mod tests {
         let e = inp.write(&buf).unwrap_err();
         assert_eq!(e.kind(), io::ErrorKind::Interrupted);
     }
+
+    /// An [`InterruptSource`] that fires once a target count of "signals" has
+    /// been observed, the way a real caller might count `SIGINT`s.
+    struct CountingSource {
+        target: usize,
+        count: std::sync::atomic::AtomicUsize,
+    }
+
+    impl InterruptSource for CountingSource {
+        fn is_interrupted(&self) -> bool {
+            self.count.load(Ordering::SeqCst) >= self.target
+        }
+    }
+
+    #[test]
+    fn test_read_custom_interrupt_source() {
+        let source = CountingSource {
+            target: 1,
+            count: std::sync::atomic::AtomicUsize::new(1),
+        };
+        let mut inp = Interruptable::new(Mock::new(Ok(vec![42; 100]), None), source);
+        let mut buf = vec![0; 42];
+
+        let e = inp.read(&mut buf).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_read_custom_interrupt_source_not_yet_fired() {
+        let source = CountingSource {
+            target: 1,
+            count: std::sync::atomic::AtomicUsize::new(0),
+        };
+        let mut inp = Interruptable::new(Mock::new(Ok(vec![42; 100]), None), source);
+        let mut buf = vec![0; 42];
+
+        assert!(matches!(inp.read(&mut buf), Ok(42)));
+    }
+
+    #[test]
+    fn test_fill_buf_normal() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut inp = Interruptable::new(io::Cursor::new(vec![1, 2, 3]), flag);
+
+        assert_eq!(inp.fill_buf().unwrap(), &[1, 2, 3]);
+        inp.consume(3);
+        assert_eq!(inp.fill_buf().unwrap(), &[] as &[u8]);
+    }
+
+    #[test]
+    fn test_fill_buf_pre_interrupt() {
+        let flag = Arc::new(AtomicBool::new(true));
+        let mut inp = Interruptable::new(io::Cursor::new(vec![1, 2, 3]), flag);
+
+        let e = inp.fill_buf().unwrap_err();
+        assert_eq!(
+            InterruptError::downcast(&e),
+            Some(InterruptError::CancelledBeforeCall)
+        );
+    }
+
+    #[test]
+    fn test_seek_normal() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut inp = Interruptable::new(io::Cursor::new(vec![1, 2, 3]), flag);
+
+        assert_eq!(inp.seek(io::SeekFrom::Start(2)).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_seek_pre_interrupt() {
+        let flag = Arc::new(AtomicBool::new(true));
+        let mut inp = Interruptable::new(io::Cursor::new(vec![1, 2, 3]), flag);
+
+        let e = inp.seek(io::SeekFrom::Start(2)).unwrap_err();
+        assert_eq!(
+            InterruptError::downcast(&e),
+            Some(InterruptError::CancelledBeforeCall)
+        );
+    }
+
+    #[test]
+    fn test_read_to_end_normal() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut inp = Interruptable::new(io::Cursor::new(vec![1, 2, 3]), flag);
+        let mut buf = Vec::new();
+
+        assert_eq!(inp.read_to_end(&mut buf).unwrap(), 3);
+        assert_eq!(buf, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_read_to_end_pre_interrupt() {
+        let flag = Arc::new(AtomicBool::new(true));
+        let mut inp = Interruptable::new(io::Cursor::new(vec![1, 2, 3]), flag);
+        let mut buf = Vec::new();
+
+        let e = inp.read_to_end(&mut buf).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_read_vectored_normal() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut inp = Interruptable::new(io::Cursor::new(vec![1, 2, 3, 4]), flag);
+        let mut a = [0; 2];
+        let mut b = [0; 2];
+        let mut bufs = [io::IoSliceMut::new(&mut a), io::IoSliceMut::new(&mut b)];
+
+        assert_eq!(inp.read_vectored(&mut bufs).unwrap(), 4);
+        assert_eq!(a, [1, 2]);
+        assert_eq!(b, [3, 4]);
+    }
+
+    #[test]
+    fn test_write_vectored_normal() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut inp = Interruptable::new(io::Cursor::new(Vec::new()), flag);
+        let a = [1, 2];
+        let b = [3, 4];
+        let bufs = [io::IoSlice::new(&a), io::IoSlice::new(&b)];
+
+        assert_eq!(inp.write_vectored(&bufs).unwrap(), 4);
+    }
+
+    /// An [`InterruptSource`] that stays clear for `remaining` checks, then
+    /// fires on every check after that -- used to land an interruption in
+    /// the middle of a chunked transfer.
+    struct FlipAfter {
+        remaining: std::sync::atomic::AtomicUsize,
+    }
+
+    impl InterruptSource for FlipAfter {
+        fn is_interrupted(&self) -> bool {
+            self.remaining
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                    (n > 0).then(|| n - 1)
+                })
+                .is_err()
+        }
+    }
+
+    #[test]
+    fn test_read_partial_progress() {
+        let source = FlipAfter {
+            remaining: std::sync::atomic::AtomicUsize::new(1),
+        };
+        let mut inp = Interruptable::with_partial_progress(
+            io::Cursor::new(vec![1, 2, 3, 4, 5, 6]),
+            source,
+            2,
+        );
+        let mut buf = vec![0; 6];
+
+        assert_eq!(inp.read(&mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], &[1, 2]);
+    }
+
+    #[test]
+    fn test_read_partial_progress_none_moved() {
+        let source = FlipAfter {
+            remaining: std::sync::atomic::AtomicUsize::new(0),
+        };
+        let mut inp =
+            Interruptable::with_partial_progress(io::Cursor::new(vec![1, 2, 3, 4]), source, 2);
+        let mut buf = vec![0; 4];
+
+        let e = inp.read(&mut buf).unwrap_err();
+        assert_eq!(
+            InterruptError::downcast(&e),
+            Some(InterruptError::CancelledBeforeCall)
+        );
+    }
+
+    #[test]
+    fn test_write_partial_progress() {
+        let source = FlipAfter {
+            remaining: std::sync::atomic::AtomicUsize::new(1),
+        };
+        let mut inp = Interruptable::with_partial_progress(io::Cursor::new(Vec::new()), source, 2);
+        let buf = vec![1, 2, 3, 4, 5, 6];
+
+        assert_eq!(inp.write(&buf).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_read_no_partial_progress_uninterrupted() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut inp =
+            Interruptable::with_partial_progress(io::Cursor::new(vec![1, 2, 3, 4, 5, 6]), flag, 2);
+        let mut buf = vec![0; 6];
+
+        assert_eq!(inp.read(&mut buf).unwrap(), 6);
+        assert_eq!(buf, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    /// An `io::Read`/`io::Write` that succeeds once, then fails with a
+    /// genuine (non-interrupt) error -- chunk0-5 must still report the
+    /// bytes moved by the first chunk rather than discarding them.
+    struct Flaky {
+        chunks: Vec<io::Result<Vec<u8>>>,
+    }
+
+    impl Read for Flaky {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.chunks.remove(0) {
+                Ok(data) => {
+                    let len = std::cmp::min(buf.len(), data.len());
+                    buf[..len].copy_from_slice(&data[..len]);
+                    Ok(len)
+                }
+                Err(e) => Err(e),
+            }
+        }
+    }
+
+    impl Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            match self.chunks.remove(0) {
+                Ok(_) => Ok(buf.len()),
+                Err(e) => Err(e),
+            }
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_read_partial_progress_survives_non_interrupt_error() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let flaky = Flaky {
+            chunks: vec![
+                Ok(vec![1, 2]),
+                Err(io::Error::from(io::ErrorKind::BrokenPipe)),
+            ],
+        };
+        let mut inp = Interruptable::with_partial_progress(flaky, flag, 2);
+        let mut buf = vec![0; 4];
+
+        assert_eq!(inp.read(&mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], &[1, 2]);
+    }
+
+    #[test]
+    fn test_write_partial_progress_survives_non_interrupt_error() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let flaky = Flaky {
+            chunks: vec![
+                Ok(Vec::new()),
+                Err(io::Error::from(io::ErrorKind::BrokenPipe)),
+            ],
+        };
+        let mut inp = Interruptable::with_partial_progress(flaky, flag, 2);
+        let buf = vec![1, 2, 3, 4];
+
+        assert_eq!(inp.write(&buf).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_read_to_end_honors_chunk_size() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut inp =
+            Interruptable::with_partial_progress(io::Cursor::new(vec![1, 2, 3, 4, 5]), flag, 2);
+        let mut buf = Vec::new();
+
+        assert_eq!(inp.read_to_end(&mut buf).unwrap(), 5);
+        assert_eq!(buf, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_read_to_end_partial_progress() {
+        let source = FlipAfter {
+            remaining: std::sync::atomic::AtomicUsize::new(1),
+        };
+        let mut inp = Interruptable::with_partial_progress(
+            io::Cursor::new(vec![1, 2, 3, 4, 5, 6]),
+            source,
+            2,
+        );
+        let mut buf = Vec::new();
+
+        assert_eq!(inp.read_to_end(&mut buf).unwrap(), 2);
+        assert_eq!(buf, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_read_vectored_honors_chunk_size() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut inp =
+            Interruptable::with_partial_progress(io::Cursor::new(vec![1, 2, 3, 4]), flag, 2);
+        let mut a = [0; 4];
+        let mut bufs = [io::IoSliceMut::new(&mut a)];
+
+        assert_eq!(inp.read_vectored(&mut bufs).unwrap(), 4);
+        assert_eq!(a, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_write_vectored_honors_chunk_size() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut inp = Interruptable::with_partial_progress(io::Cursor::new(Vec::new()), flag, 2);
+        let a = [1, 2, 3, 4];
+        let bufs = [io::IoSlice::new(&a)];
+
+        assert_eq!(inp.write_vectored(&bufs).unwrap(), 4);
+    }
 }
@@ -0,0 +1,133 @@
+/* Copyright 2022 Ivan Boldyrev
+ *
+ * Licensed under the MIT License.
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+ * FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+ * IN THE SOFTWARE.
+ */
+
+//! `tokio::io::AsyncRead`/`AsyncWrite` impls for [`Interruptable`], gated
+//! behind the `tokio` feature. Same flag-check semantics as the sync impls:
+//! the interrupt source is checked before delegating to the inner poll, and
+//! the result is run back through `check_again`. `poll_shutdown` is the
+//! exception: it is passed straight to the inner writer with no check.
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::{InterruptError, InterruptSource, Interruptable};
+
+impl<IO: AsyncRead + Unpin, H: InterruptSource + Unpin> AsyncRead for Interruptable<IO, H> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        if this.interrupt_flag.is_interrupted() {
+            return Poll::Ready(Err(Self::das_error(InterruptError::CancelledBeforeCall)));
+        }
+        match Pin::new(&mut this.inner).poll_read(cx, buf) {
+            Poll::Ready(Err(e)) => Poll::Ready(Err(Self::check_again(&this.interrupt_flag, e))),
+            other => other,
+        }
+    }
+}
+
+impl<IO: AsyncWrite + Unpin, H: InterruptSource + Unpin> AsyncWrite for Interruptable<IO, H> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        if this.interrupt_flag.is_interrupted() {
+            return Poll::Ready(Err(Self::das_error(InterruptError::CancelledBeforeCall)));
+        }
+        match Pin::new(&mut this.inner).poll_write(cx, buf) {
+            Poll::Ready(Err(e)) => Poll::Ready(Err(Self::check_again(&this.interrupt_flag, e))),
+            other => other,
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        if this.interrupt_flag.is_interrupted() {
+            return Poll::Ready(Err(Self::das_error(InterruptError::CancelledBeforeCall)));
+        }
+        match Pin::new(&mut this.inner).poll_flush(cx) {
+            Poll::Ready(Err(e)) => Poll::Ready(Err(Self::check_again(&this.interrupt_flag, e))),
+            other => other,
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{atomic::AtomicBool, Arc};
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use crate::Interruptable;
+
+    #[tokio::test]
+    async fn test_poll_read_normal() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let data: &[u8] = b"hello";
+        let mut inp = Interruptable::new(data, flag);
+        let mut buf = [0u8; 5];
+
+        inp.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"hello");
+    }
+
+    #[tokio::test]
+    async fn test_poll_read_pre_interrupt() {
+        let flag = Arc::new(AtomicBool::new(true));
+        let data: &[u8] = b"hello";
+        let mut inp = Interruptable::new(data, flag);
+        let mut buf = [0u8; 5];
+
+        let e = inp.read_exact(&mut buf).await.unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::Other);
+    }
+
+    #[tokio::test]
+    async fn test_poll_write_normal() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut inp = Interruptable::new(Vec::new(), flag);
+
+        inp.write_all(b"hi").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_poll_write_pre_interrupt() {
+        let flag = Arc::new(AtomicBool::new(true));
+        let mut inp = Interruptable::new(Vec::new(), flag);
+
+        let e = inp.write_all(b"hi").await.unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::Other);
+    }
+}